@@ -0,0 +1,182 @@
+use super::{openai::extract_host, set_proxy, set_tls, Client, ClientConfig, ModelInfo, TlsConfig};
+use crate::{
+    config::SharedConfig,
+    repl::ReplyStreamHandler,
+};
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use reqwest::{Client as ReqwestClient, ClientBuilder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const API_BASE: &str = "http://localhost:8080/v1";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LocalAIConfig {
+    pub api_key: Option<String>,
+    pub api_base: Option<String>,
+    pub models: Option<Vec<LocalAIModel>>,
+    pub proxy: Option<String>,
+    pub proxy_username: Option<String>,
+    pub proxy_password: Option<String>,
+    pub proxy_no_proxy: Option<Vec<String>>,
+    pub tls: Option<TlsConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LocalAIModel {
+    pub name: String,
+    pub max_tokens: usize,
+}
+
+pub struct LocalAIClient {
+    global_config: SharedConfig,
+    config: LocalAIConfig,
+    client: ReqwestClient,
+}
+
+impl LocalAIClient {
+    pub fn name() -> &'static str {
+        "localai"
+    }
+
+    pub fn init(global_config: SharedConfig) -> Option<Box<dyn Client>> {
+        let model_info = global_config.read().model_info.clone();
+        if model_info.client != Self::name() {
+            return None;
+        }
+        let config = match global_config.read().clients.get(model_info.index) {
+            Some(ClientConfig::LocalAI(config)) => config.clone(),
+            _ => return None,
+        };
+        let client = Self::build_client(&config).ok()?;
+        Some(Box::new(Self {
+            global_config,
+            config,
+            client,
+        }))
+    }
+
+    pub fn list_models(config: &LocalAIConfig, index: usize) -> Vec<ModelInfo> {
+        config
+            .models
+            .clone()
+            .unwrap_or_default()
+            .iter()
+            .map(|model| ModelInfo::new(Self::name(), &model.name, model.max_tokens, index))
+            .collect()
+    }
+
+    pub fn create_config() -> Result<String> {
+        Ok(format!(
+            "clients:\n  - type: {}\n    api_key: ~\n    api_base: {API_BASE}\n    proxy: ~\n",
+            Self::name()
+        ))
+    }
+
+    fn build_client(config: &LocalAIConfig) -> Result<ReqwestClient> {
+        let host = extract_host(&api_base(config));
+        let mut builder = set_proxy(
+            ClientBuilder::new(),
+            &config.proxy,
+            &config.proxy_username,
+            &config.proxy_password,
+            &host,
+            &config.proxy_no_proxy,
+        )?;
+        if let Some(tls) = &config.tls {
+            builder = set_tls(builder, tls)?;
+        }
+        builder
+            .build()
+            .with_context(|| "Failed to build LocalAI client")
+    }
+
+    fn build_request(&self, content: &str, stream: bool) -> reqwest::RequestBuilder {
+        let mut request = self
+            .client
+            .post(format!("{}/chat/completions", api_base(&self.config)))
+            .json(&json!({
+                "model": self.global_config.read().model_info.name,
+                "messages": [{ "role": "user", "content": content }],
+                "stream": stream,
+            }));
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        request
+    }
+}
+
+#[async_trait]
+impl Client for LocalAIClient {
+    fn get_config(&self) -> &SharedConfig {
+        &self.global_config
+    }
+
+    async fn send_message_inner(&self, content: &str) -> Result<String> {
+        let data: Value = self
+            .build_request(content, false)
+            .send()
+            .await
+            .with_context(|| "Failed to send request to LocalAI")?
+            .error_for_status()
+            .with_context(|| "LocalAI returned an error")?
+            .json()
+            .await
+            .with_context(|| "Invalid LocalAI response")?;
+        data["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|content| content.to_string())
+            .ok_or_else(|| anyhow!("Unexpected LocalAI response: {data}"))
+    }
+
+    async fn send_message_streaming_inner(
+        &self,
+        content: &str,
+        handler: &mut ReplyStreamHandler,
+    ) -> Result<()> {
+        let response = self
+            .build_request(content, true)
+            .send()
+            .await
+            .with_context(|| "Failed to send request to LocalAI")?
+            .error_for_status()
+            .with_context(|| "LocalAI returned an error")?;
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.with_context(|| "Failed to read LocalAI stream")?;
+            buffer.push_str(&String::from_utf8_lossy(&chunk));
+            while let Some(pos) = buffer.find('\n') {
+                let line = buffer[..pos].trim().to_string();
+                buffer.drain(..=pos);
+                let Some(data) = line.strip_prefix("data:") else {
+                    continue;
+                };
+                let data = data.trim();
+                if data == "[DONE]" {
+                    return Ok(());
+                }
+                if data.is_empty() {
+                    continue;
+                }
+                let value: Value = serde_json::from_str(data)
+                    .with_context(|| format!("Invalid LocalAI stream chunk `{data}`"))?;
+                if let Some(text) = value["choices"][0]["delta"]["content"].as_str() {
+                    handler.text(text)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn api_base(config: &LocalAIConfig) -> String {
+    config
+        .api_base
+        .clone()
+        .unwrap_or_else(|| API_BASE.to_string())
+}
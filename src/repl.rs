@@ -0,0 +1,100 @@
+use anyhow::Result;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Ctrl-C state shared between the REPL loop and whatever request is currently in
+/// flight. The two flags are independent: the first Ctrl-C during a streaming reply
+/// should only cancel the upstream request and keep the partial reply (`interrupted`),
+/// while a second Ctrl-C within the grace window tears down the whole REPL (`aborted`).
+#[derive(Debug, Clone, Default)]
+pub struct SharedAbortSignal {
+    hard: Arc<AtomicBool>,
+    soft: Arc<AtomicBool>,
+}
+
+impl SharedAbortSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hard abort: quit the REPL loop entirely, not just the current request.
+    pub fn set_ctrlc(&self) {
+        self.hard.store(true, Ordering::SeqCst);
+    }
+
+    pub fn aborted(&self) -> bool {
+        self.hard.load(Ordering::SeqCst)
+    }
+
+    /// Soft interrupt: the in-flight streamed reply was cut short and flushed, but the
+    /// REPL loop should keep running. The loop observes this after `send_message_streaming`
+    /// returns to print its own notice and reset the flag via `clear_interrupt`.
+    pub fn set_interrupted(&self) {
+        self.soft.store(true, Ordering::SeqCst);
+    }
+
+    pub fn interrupted(&self) -> bool {
+        self.soft.load(Ordering::SeqCst)
+    }
+
+    pub fn clear_interrupt(&self) {
+        self.soft.store(false, Ordering::SeqCst);
+    }
+}
+
+pub struct ReplyStreamHandler {
+    buffer: String,
+    abort: SharedAbortSignal,
+}
+
+impl ReplyStreamHandler {
+    pub fn new(abort: SharedAbortSignal) -> Self {
+        Self {
+            buffer: String::new(),
+            abort,
+        }
+    }
+
+    pub fn text(&mut self, text: &str) -> Result<()> {
+        print!("{text}");
+        self.buffer.push_str(text);
+        Ok(())
+    }
+
+    pub fn done(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn get_abort(&self) -> SharedAbortSignal {
+        self.abort.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interrupted_is_independent_of_hard_abort() {
+        let abort = SharedAbortSignal::new();
+        assert!(!abort.interrupted());
+        assert!(!abort.aborted());
+
+        abort.set_interrupted();
+        assert!(abort.interrupted());
+        assert!(!abort.aborted());
+
+        abort.clear_interrupt();
+        assert!(!abort.interrupted());
+    }
+
+    #[test]
+    fn set_ctrlc_sets_hard_abort_only() {
+        let abort = SharedAbortSignal::new();
+        abort.set_ctrlc();
+        assert!(abort.aborted());
+        assert!(!abort.interrupted());
+    }
+}